@@ -0,0 +1,295 @@
+use std::fmt::Display;
+
+use crate::{Error, ErrorType};
+
+/// The `SameSite` attribute of a `Set-Cookie` header, restricting when the
+/// cookie is sent with cross-site requests.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl Display for SameSite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SameSite::Strict => write!(f, "Strict"),
+            SameSite::Lax => write!(f, "Lax"),
+            SameSite::None => write!(f, "None"),
+        }
+    }
+}
+
+/// A single cookie, either parsed off an incoming `Cookie` request header
+/// (in which case only `name`/`value` are populated) or built up to send
+/// back as a `Set-Cookie` response header.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    pub path: Option<String>,
+    pub domain: Option<String>,
+    pub max_age: Option<i64>,
+    pub expires: Option<String>,
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: Option<SameSite>,
+}
+
+impl Cookie {
+    pub fn new(name: &str, value: &str) -> Self {
+        Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            path: None,
+            domain: None,
+            max_age: None,
+            expires: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+        }
+    }
+
+    pub fn path(self, path: &str) -> Self {
+        Self {
+            path: Some(path.to_string()),
+            ..self
+        }
+    }
+
+    pub fn domain(self, domain: &str) -> Self {
+        Self {
+            domain: Some(domain.to_string()),
+            ..self
+        }
+    }
+
+    pub fn max_age(self, seconds: i64) -> Self {
+        Self {
+            max_age: Some(seconds),
+            ..self
+        }
+    }
+
+    pub fn expires(self, expires: &str) -> Self {
+        Self {
+            expires: Some(expires.to_string()),
+            ..self
+        }
+    }
+
+    pub fn secure(self) -> Self {
+        Self {
+            secure: true,
+            ..self
+        }
+    }
+
+    pub fn http_only(self) -> Self {
+        Self {
+            http_only: true,
+            ..self
+        }
+    }
+
+    pub fn same_site(self, same_site: SameSite) -> Self {
+        Self {
+            same_site: Some(same_site),
+            ..self
+        }
+    }
+
+    /// Parses the value of a `Cookie` request header, a `;`-separated list
+    /// of `name=value` pairs with optional OWS around each pair.
+    ///
+    /// cookie-header = "Cookie:" OWS cookie-string OWS
+    /// cookie-string = cookie-pair *( ";" SP cookie-pair )
+    pub fn parse_header(header_value: &str) -> Result<Vec<Cookie>, Error> {
+        header_value
+            .split(';')
+            .map(|pair| {
+                let pair = pair.trim();
+                let (name, value) = pair.split_once('=').ok_or_else(|| Error {
+                    error: ErrorType::ParseError,
+                    error_msg: "invalid cookie pair".to_string(),
+                })?;
+
+                let value = percent_decode(value.trim())?;
+                Ok(Cookie::new(name.trim(), &value))
+            })
+            .collect()
+    }
+}
+
+impl Display for Cookie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.name, percent_encode(&self.value))?;
+
+        if let Some(path) = &self.path {
+            write!(f, "; Path={}", path)?;
+        }
+        if let Some(domain) = &self.domain {
+            write!(f, "; Domain={}", domain)?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age)?;
+        }
+        if let Some(expires) = &self.expires {
+            write!(f, "; Expires={}", expires)?;
+        }
+        if self.secure {
+            write!(f, "; Secure")?;
+        }
+        if self.http_only {
+            write!(f, "; HttpOnly")?;
+        }
+        if let Some(same_site) = &self.same_site {
+            write!(f, "; SameSite={}", same_site)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Bytes the USERINFO percent-encode set requires escaping: the C0 control
+/// set, everything above ASCII, and the handful of delimiters that would
+/// otherwise be mistaken for cookie/URL syntax. `,` is additionally escaped
+/// since, like `;` (already in that set), it delimits cookie-pairs and would
+/// otherwise break round-tripping through the `Cookie` header grammar. `%` is escaped too,
+/// since otherwise a literal `%` in a value would be misread by
+/// `percent_decode` as the start of an escape sequence.
+fn needs_percent_encoding(byte: u8) -> bool {
+    !(0x20..0x7F).contains(&byte)
+        || matches!(
+            byte,
+            b' ' | b'"'
+                | b'#'
+                | b'%'
+                | b'/'
+                | b':'
+                | b';'
+                | b','
+                | b'<'
+                | b'='
+                | b'>'
+                | b'?'
+                | b'@'
+                | b'['
+                | b'\\'
+                | b']'
+                | b'^'
+                | b'`'
+                | b'{'
+                | b'|'
+                | b'}'
+        )
+}
+
+fn percent_encode(input: &str) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        if needs_percent_encoding(byte) {
+            encoded.push_str(&format!("%{:02X}", byte));
+        } else {
+            encoded.push(byte as char);
+        }
+    }
+    encoded
+}
+
+fn percent_decode(input: &str) -> Result<String, Error> {
+    let parse_error = || Error {
+        error: ErrorType::ParseError,
+        error_msg: "invalid percent-encoding in cookie value".to_string(),
+    };
+
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = input.get(i + 1..i + 3).ok_or_else(parse_error)?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| parse_error())?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| parse_error())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cookie, ErrorType, SameSite};
+
+    #[test]
+    fn parse_cookie_header() {
+        let cookies = Cookie::parse_header("session=abc123; theme=dark").unwrap();
+
+        assert_eq!(cookies, vec![Cookie::new("session", "abc123"), Cookie::new("theme", "dark")]);
+    }
+
+    #[test]
+    fn parse_cookie_header_percent_decodes_values() {
+        let cookies = Cookie::parse_header("greeting=hello%20world%3B%20hi").unwrap();
+
+        assert_eq!(cookies, vec![Cookie::new("greeting", "hello world; hi")]);
+    }
+
+    #[test]
+    fn parse_cookie_header_invalid_percent_encoding_is_error() {
+        let error = Cookie::parse_header("session=abc%zz").unwrap_err();
+
+        assert_eq!(error.error, ErrorType::ParseError)
+    }
+
+    #[test]
+    fn display_percent_encodes_value() {
+        let cookie = Cookie::new("greeting", "hello world; hi");
+
+        assert_eq!(cookie.to_string(), "greeting=hello%20world%3B%20hi");
+    }
+
+    #[test]
+    fn display_percent_encodes_literal_percent() {
+        let cookie = Cookie::new("promo", "DISC%41NT");
+
+        assert_eq!(cookie.to_string(), "promo=DISC%2541NT");
+    }
+
+    #[test]
+    fn round_trips_value_containing_percent() {
+        let cookie = Cookie::new("promo", "DISC%41NT");
+        let cookies = Cookie::parse_header(&cookie.to_string()).unwrap();
+
+        assert_eq!(cookies, vec![cookie]);
+    }
+
+    #[test]
+    fn round_trips_value_ending_in_percent() {
+        let cookie = Cookie::new("discount", "100%");
+        let cookies = Cookie::parse_header(&cookie.to_string()).unwrap();
+
+        assert_eq!(cookies, vec![cookie]);
+    }
+
+    #[test]
+    fn display_includes_attributes() {
+        let cookie = Cookie::new("session", "abc123")
+            .path("/")
+            .domain("example.com")
+            .max_age(3600)
+            .secure()
+            .http_only()
+            .same_site(SameSite::Strict);
+
+        assert_eq!(
+            cookie.to_string(),
+            "session=abc123; Path=/; Domain=example.com; Max-Age=3600; Secure; HttpOnly; SameSite=Strict"
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::{fmt::Display, io};
 #[derive(Debug, PartialEq, Eq)]
 pub enum ErrorType {
     ParseError,
+    SerializationError,
 }
 
 #[derive(Debug)]
@@ -15,6 +16,7 @@ impl Display for ErrorType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ErrorType::ParseError => write!(f, "{}", String::from("PARSER ERROR")),
+            ErrorType::SerializationError => write!(f, "{}", String::from("SERIALIZATION ERROR")),
         }
     }
 }
@@ -1,12 +1,20 @@
+use std::fmt::Display;
+
 use crate::{Error, ErrorType};
 
 /// field-line   = field-name ":" OWS field-value OWS
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Header {
     pub field_name: String,
     pub field_value: String,
 }
 
+impl Display for Header {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}\r\n", self.field_name, self.field_value)
+    }
+}
+
 impl Header {
     /// Creates a header from a key and a value
     ///
@@ -153,7 +161,7 @@ mod test {
     fn header_from_header_section() {
         let header_section = "Host: 127.0.0.1:3000\r\nAccept: */*\r\nContent-Type: application/json\r\nContent-Length: 23";
 
-        let expected_headers = vec![
+        let expected_headers = [
             Header {
                 field_name: "Host".to_string(),
                 field_value: "127.0.0.1:3000".to_string(),
@@ -0,0 +1,166 @@
+use std::fmt::Display;
+
+use crate::{Error, Header};
+
+/// A case-insensitive, order-preserving, multi-value collection of headers.
+///
+/// Field-names are compared by their ASCII-lowercased form, per RFC 7230,
+/// while the casing each entry was inserted with is kept around for
+/// serialization. Insertion order is preserved and repeated field-names
+/// (e.g. multiple `Set-Cookie` headers) are all retained.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct HeaderMap {
+    entries: Vec<Header>,
+}
+
+impl HeaderMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a header section (field-lines separated by `\r\n`, no
+    /// trailing blank line) into a `HeaderMap`.
+    pub fn from_section(header_section: String) -> Result<HeaderMap, Error> {
+        Ok(HeaderMap {
+            entries: Header::from_section(header_section)?,
+        })
+    }
+
+    /// Removes any existing values for `field_name` and inserts a single
+    /// new value.
+    pub fn insert(&mut self, field_name: &str, field_value: &str) {
+        self.remove(field_name);
+        self.append(field_name, field_value);
+    }
+
+    /// Adds another value for `field_name` without removing existing ones.
+    pub fn append(&mut self, field_name: &str, field_value: &str) {
+        self.entries.push(Header::new(field_name, field_value));
+    }
+
+    /// Returns the first value stored for `field_name`, compared
+    /// case-insensitively.
+    pub fn get(&self, field_name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|h| h.field_name.eq_ignore_ascii_case(field_name))
+            .map(|h| h.field_value.as_str())
+    }
+
+    /// Returns every value stored for `field_name`, in insertion order.
+    pub fn get_all(&self, field_name: &str) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|h| h.field_name.eq_ignore_ascii_case(field_name))
+            .map(|h| h.field_value.as_str())
+            .collect()
+    }
+
+    /// Removes every value stored for `field_name`.
+    pub fn remove(&mut self, field_name: &str) {
+        self.entries
+            .retain(|h| !h.field_name.eq_ignore_ascii_case(field_name));
+    }
+
+    pub fn contains(&self, field_name: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|h| h.field_name.eq_ignore_ascii_case(field_name))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Header> {
+        self.entries.iter()
+    }
+}
+
+impl Extend<Header> for HeaderMap {
+    fn extend<T: IntoIterator<Item = Header>>(&mut self, iter: T) {
+        self.entries.extend(iter)
+    }
+}
+
+impl From<Vec<Header>> for HeaderMap {
+    fn from(entries: Vec<Header>) -> Self {
+        HeaderMap { entries }
+    }
+}
+
+impl Display for HeaderMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for header in &self.entries {
+            write!(f, "{}", header)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Header, HeaderMap};
+
+    #[test]
+    fn insert_overwrites_existing_values() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Length", "10");
+        headers.insert("content-length", "20");
+
+        assert_eq!(headers.get_all("Content-Length"), vec!["20"]);
+    }
+
+    #[test]
+    fn append_keeps_multiple_values() {
+        let mut headers = HeaderMap::new();
+        headers.append("Set-Cookie", "a=1");
+        headers.append("set-cookie", "b=2");
+
+        assert_eq!(headers.get_all("Set-Cookie"), vec!["a=1", "b=2"]);
+    }
+
+    #[test]
+    fn get_is_case_insensitive() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Type", "application/json");
+
+        assert_eq!(headers.get("content-type"), Some("application/json"));
+        assert_eq!(headers.get("CONTENT-TYPE"), Some("application/json"));
+    }
+
+    #[test]
+    fn remove_and_contains() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Host", "127.0.0.1:3000");
+        assert!(headers.contains("host"));
+
+        headers.remove("HOST");
+        assert!(!headers.contains("host"));
+    }
+
+    #[test]
+    fn display_preserves_original_casing_and_order() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Host", "127.0.0.1:3000");
+        headers.append("Set-Cookie", "a=1");
+        headers.append("Set-Cookie", "b=2");
+
+        assert_eq!(
+            headers.to_string(),
+            "Host: 127.0.0.1:3000\r\nSet-Cookie: a=1\r\nSet-Cookie: b=2\r\n"
+        );
+    }
+
+    #[test]
+    fn from_section_parses_field_lines() {
+        let headers =
+            HeaderMap::from_section("Host: 127.0.0.1:3000\r\nAccept: */*".to_string()).unwrap();
+
+        assert_eq!(headers.get("Host"), Some("127.0.0.1:3000"));
+        assert_eq!(headers.get("Accept"), Some("*/*"));
+    }
+
+    #[test]
+    fn from_vec_of_headers() {
+        let headers: HeaderMap = vec![Header::new("Host", "127.0.0.1:3000")].into();
+
+        assert_eq!(headers.get("Host"), Some("127.0.0.1:3000"));
+    }
+}
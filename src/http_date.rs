@@ -0,0 +1,176 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Error, ErrorType};
+
+/// Formats `time` as an IMF-fixdate, the only format HTTP-date producers
+/// are permitted to generate (RFC 9110 §5.6.7), e.g.
+/// `Sun, 06 Nov 1994 08:49:37 GMT`.
+pub(crate) fn format_imf_fixdate(time: SystemTime) -> String {
+    let total_secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs() as i64;
+
+    let days = total_secs.div_euclid(86400);
+    let secs_of_day = total_secs.rem_euclid(86400);
+
+    let (year, month, day) = civil_from_days(days);
+    let hour = secs_of_day / 3600;
+    let minute = (secs_of_day % 3600) / 60;
+    let second = secs_of_day % 60;
+
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        weekday_name(days),
+        day,
+        month_name(month),
+        year,
+        hour,
+        minute,
+        second
+    )
+}
+
+/// Parses an IMF-fixdate such as `Sun, 06 Nov 1994 08:49:37 GMT`.
+pub(crate) fn parse_imf_fixdate(date: &str) -> Result<SystemTime, Error> {
+    let parse_error = || Error {
+        error: ErrorType::ParseError,
+        error_msg: format!("invalid IMF-fixdate: {}", date),
+    };
+
+    let mut parts = date.split_whitespace();
+
+    parts.next().ok_or_else(parse_error)?; // "Www,", weekday is not validated
+    let day: u32 = parts
+        .next()
+        .ok_or_else(parse_error)?
+        .parse()
+        .map_err(|_| parse_error())?;
+    let month = month_from_name(parts.next().ok_or_else(parse_error)?).ok_or_else(parse_error)?;
+    let year: i64 = parts
+        .next()
+        .ok_or_else(parse_error)?
+        .parse()
+        .map_err(|_| parse_error())?;
+
+    let time = parts.next().ok_or_else(parse_error)?;
+    let mut time_parts = time.split(':');
+    let hour: i64 = time_parts
+        .next()
+        .ok_or_else(parse_error)?
+        .parse()
+        .map_err(|_| parse_error())?;
+    let minute: i64 = time_parts
+        .next()
+        .ok_or_else(parse_error)?
+        .parse()
+        .map_err(|_| parse_error())?;
+    let second: i64 = time_parts
+        .next()
+        .ok_or_else(parse_error)?
+        .parse()
+        .map_err(|_| parse_error())?;
+
+    if parts.next() != Some("GMT") {
+        return Err(parse_error());
+    }
+
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86400 + hour * 3600 + minute * 60 + second;
+    if secs < 0 {
+        return Err(parse_error());
+    }
+
+    Ok(UNIX_EPOCH + Duration::from_secs(secs as u64))
+}
+
+/// Howard Hinnant's `civil_from_days`: converts a day count since the Unix
+/// epoch (1970-01-01) into a proleptic-Gregorian `(year, month, day)`.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = if month <= 2 { y + 1 } else { y };
+    (year, month, day)
+}
+
+/// The inverse of [`civil_from_days`].
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = month as i64;
+    let d = day as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn weekday_name(days_since_epoch: i64) -> &'static str {
+    const NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    NAMES[(days_since_epoch + 4).rem_euclid(7) as usize]
+}
+
+fn month_name(month: u32) -> &'static str {
+    const NAMES: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    NAMES[(month - 1) as usize]
+}
+
+fn month_from_name(name: &str) -> Option<u32> {
+    Some(match name {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_imf_fixdate, parse_imf_fixdate};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn formats_known_instant() {
+        // 1994-11-06T08:49:37Z, the example date from RFC 9110 §5.6.7.
+        let time = UNIX_EPOCH + Duration::from_secs(784111777);
+
+        assert_eq!(format_imf_fixdate(time), "Sun, 06 Nov 1994 08:49:37 GMT");
+    }
+
+    #[test]
+    fn parses_known_date() {
+        let time = parse_imf_fixdate("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+
+        assert_eq!(time, UNIX_EPOCH + Duration::from_secs(784111777));
+    }
+
+    #[test]
+    fn round_trips_the_unix_epoch() {
+        assert_eq!(format_imf_fixdate(UNIX_EPOCH), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(parse_imf_fixdate("Thu, 01 Jan 1970 00:00:00 GMT").unwrap(), UNIX_EPOCH);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_input() {
+        assert!(parse_imf_fixdate("not a date").is_err());
+        assert!(parse_imf_fixdate("Sun, 06 Nov 1994 08:49:37 EST").is_err());
+    }
+}
@@ -1,9 +1,18 @@
+mod cookie;
 mod error;
 mod header;
+mod header_map;
+mod http_date;
 mod method;
 mod request;
+mod response;
+mod status;
 
+pub use cookie::{Cookie, SameSite};
 pub use error::{Error, ErrorType};
 pub use header::Header;
+pub use header_map::HeaderMap;
 pub use method::Method;
 pub use request::Request;
+pub use response::Response;
+pub use status::Status;
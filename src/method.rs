@@ -12,7 +12,13 @@ pub enum Method {
 
 impl Display for Method {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_string())
+        let method = match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Delete => "DELETE",
+            Method::Put => "PUT",
+        };
+        write!(f, "{}", method)
     }
 }
 
@@ -49,4 +55,18 @@ mod tests {
             assert_eq!(Method::from_string(string).unwrap(), method)
         }
     }
+
+    #[test]
+    fn display() {
+        let method_string = vec![
+            (Method::Get, "GET"),
+            (Method::Post, "POST"),
+            (Method::Delete, "DELETE"),
+            (Method::Put, "PUT"),
+        ];
+
+        for (method, string) in method_string {
+            assert_eq!(method.to_string(), string)
+        }
+    }
 }
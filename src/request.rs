@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use crate::error::{Error, ErrorType};
-use crate::{Header, Method};
+use crate::{Cookie, Header, HeaderMap, Method};
 
 #[derive(PartialEq, Eq, Debug)]
 /// A request-line begins with a method token, followed by a single space (SP), the request-target, and another single space (SP),
@@ -10,10 +10,16 @@ use crate::{Header, Method};
 pub struct Request {
     pub method: Method,
     pub request_target: String,
+    /// The percent-decoded path component of the request-target, with the
+    /// query string (if any) removed. Used for routing.
+    pub path: String,
+    /// The percent-decoded query parameters, in the order they appeared in
+    /// the request-target.
+    pub query: Vec<(String, String)>,
     //  HTTP-version  = HTTP-name "/" DIGIT "." DIGIT
     //  HTTP-name     = %s"HTTP"
     pub protocol_version: String,
-    pub header: Vec<Header>,
+    pub header: HeaderMap,
     pub body: Option<String>,
 }
 
@@ -24,14 +30,10 @@ impl Display for Request {
             None => String::from(""),
         };
 
-        let mut headers = String::new();
-        for h in &self.header {
-            headers.push_str(&h.to_string());
-        }
         write!(
             f,
             "{} {} {}\r\n{}\r\n{}\r\n\r\n",
-            self.method, self.request_target, self.protocol_version, headers, body
+            self.method, self.request_target, self.protocol_version, self.header, body
         )
     }
 }
@@ -41,16 +43,86 @@ impl Request {
         Self::parse(request_string)
     }
 
+    /// The parsed `Content-Length` header, if present and valid.
+    pub fn content_length(&self) -> Option<usize> {
+        self.header.get("content-length")?.parse().ok()
+    }
+
+    /// The `Content-Type` header value, if present.
+    pub fn content_type(&self) -> Option<&str> {
+        self.header.get("content-type")
+    }
+
+    /// Parses the `Cookie` request header, if any, into its individual
+    /// cookies.
+    pub fn cookies(&self) -> Result<Vec<Cookie>, Error> {
+        match self.header.get("cookie") {
+            Some(value) => Cookie::parse_header(value),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// The parsed query parameters, in the order they appeared in the
+    /// request-target.
+    pub fn query(&self) -> &[(String, String)] {
+        &self.query
+    }
+
+    /// The first value of the query parameter named `name`, if present.
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Deserializes the body as JSON, requiring a `Content-Type` of
+    /// `application/json`.
+    #[cfg(feature = "json")]
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, Error> {
+        self.json_with_content_types(&["application/json"])
+    }
+
+    /// Like [`Request::json`], but accepts any of `allowed_content_types`
+    /// instead of requiring exactly `application/json`.
+    #[cfg(feature = "json")]
+    pub fn json_with_content_types<T: serde::de::DeserializeOwned>(
+        &self,
+        allowed_content_types: &[&str],
+    ) -> Result<T, Error> {
+        let content_type = self.content_type().ok_or_else(|| Error {
+            error: ErrorType::ParseError,
+            error_msg: "missing Content-Type header".to_string(),
+        })?;
+
+        if !allowed_content_types
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(content_type))
+        {
+            return Err(Error {
+                error: ErrorType::ParseError,
+                error_msg: format!("unsupported Content-Type: {}", content_type),
+            });
+        }
+
+        let body = self.body.as_deref().ok_or_else(|| Error {
+            error: ErrorType::ParseError,
+            error_msg: "missing request body".to_string(),
+        })?;
+
+        serde_json::from_str(body).map_err(|e| Error {
+            error: ErrorType::SerializationError,
+            error_msg: e.to_string(),
+        })
+    }
+
     fn parse(request_string: &str) -> Result<Request, Error> {
         let parse_error = Error {
             error: ErrorType::ParseError,
             error_msg: "invalid request format".to_string(),
         };
 
-        let parts: Vec<&str> = request_string.split("\r\n").collect();
-        let mut parts_iter = parts.iter();
-
-        let start_line = match parts_iter.next() {
+        let (start_line, after_start_line) = match request_string.split_once("\r\n") {
             Some(v) => v,
             None => return Err(parse_error),
         };
@@ -60,37 +132,171 @@ impl Request {
             Err(_) => return Err(parse_error),
         };
 
-        let mut header = vec![];
-        while let Some(h) = parts_iter.next() {
-            match *h {
-                // Skip empty line between headers and body
-                "" => {
-                    break;
-                }
-                _ => header.push(Header::from_field_line(h)?),
+        // The header block ends at the first blank line; everything after
+        // that CRLF CRLF separator is raw body bytes, not more field-lines.
+        let (header_section, body_section) = match after_start_line.find("\r\n\r\n") {
+            Some(idx) => (&after_start_line[..idx], &after_start_line[idx + 4..]),
+            None => (after_start_line, ""),
+        };
+
+        let mut header: HeaderMap = if header_section.is_empty() {
+            HeaderMap::new()
+        } else {
+            HeaderMap::from_section(header_section.to_string())?
+        };
+
+        let chunked = header
+            .get("transfer-encoding")
+            .is_some_and(|v| v.eq_ignore_ascii_case("chunked"));
+
+        let body = if chunked {
+            let (body, trailers) = Self::decode_chunked(body_section)?;
+            header.extend(trailers);
+            Some(body)
+        } else {
+            let content_length = header
+                .get("content-length")
+                .map(|v| v.parse::<usize>())
+                .transpose()
+                .map_err(|_| Error {
+                    error: ErrorType::ParseError,
+                    error_msg: "invalid Content-Length".to_string(),
+                })?;
+
+            match content_length {
+                Some(len) => match body_section.get(..len) {
+                    Some(v) => Some(v.to_string()),
+                    None => {
+                        return Err(Error {
+                            error: ErrorType::ParseError,
+                            error_msg: "body shorter than Content-Length".to_string(),
+                        })
+                    }
+                },
+                None => None,
             }
-        }
+        };
 
-        let body = match parts_iter.next() {
-            Some(v) => Some(v.to_string()),
-            None => None,
+        let (raw_path, raw_query) = match path.split_once('?') {
+            Some((p, q)) => (p, q),
+            None => (path, ""),
         };
 
         Ok(Request {
             method,
             request_target: String::from(path),
+            path: Self::percent_decode(raw_path, false)?,
+            query: Self::parse_query(raw_query)?,
             protocol_version: String::from(version),
             header,
             body,
         })
     }
 
+    /// Parses an `application/x-www-form-urlencoded` query string into its
+    /// key/value pairs, in order. `+` decodes to a space and `%XX`
+    /// sequences are percent-decoded.
+    fn parse_query(query: &str) -> Result<Vec<(String, String)>, Error> {
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+
+        query
+            .split('&')
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                Ok((
+                    Self::percent_decode(key, true)?,
+                    Self::percent_decode(value, true)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Percent-decodes `input`. When `plus_as_space` is set, `+` decodes to
+    /// a space, per `application/x-www-form-urlencoded` rules.
+    fn percent_decode(input: &str, plus_as_space: bool) -> Result<String, Error> {
+        let parse_error = || Error {
+            error: ErrorType::ParseError,
+            error_msg: "invalid percent-encoding".to_string(),
+        };
+
+        let bytes = input.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'%' => {
+                    let hex = input.get(i + 1..i + 3).ok_or_else(parse_error)?;
+                    decoded.push(u8::from_str_radix(hex, 16).map_err(|_| parse_error())?);
+                    i += 3;
+                }
+                b'+' if plus_as_space => {
+                    decoded.push(b' ');
+                    i += 1;
+                }
+                b => {
+                    decoded.push(b);
+                    i += 1;
+                }
+            }
+        }
+
+        String::from_utf8(decoded).map_err(|_| parse_error())
+    }
+
+    /// Decodes a `Transfer-Encoding: chunked` body, returning the
+    /// concatenated chunk data together with any trailer fields that
+    /// followed the terminating zero-length chunk.
+    ///
+    /// chunked-body = *chunk last-chunk trailer-section CRLF
+    /// chunk        = chunk-size [ chunk-ext ] CRLF chunk-data CRLF
+    /// last-chunk   = 1*"0" [ chunk-ext ] CRLF
+    fn decode_chunked(mut data: &str) -> Result<(String, Vec<Header>), Error> {
+        let parse_error = || Error {
+            error: ErrorType::ParseError,
+            error_msg: "invalid chunked body".to_string(),
+        };
+
+        let mut body = String::new();
+        loop {
+            let (size_line, rest) = data.split_once("\r\n").ok_or_else(parse_error)?;
+            let size_str = size_line.split(';').next().unwrap_or("").trim();
+            let size = usize::from_str_radix(size_str, 16).map_err(|_| parse_error())?;
+            data = rest;
+
+            if size == 0 {
+                break;
+            }
+
+            let chunk_data = data.get(..size).ok_or_else(parse_error)?;
+            let after_chunk = data[size..].strip_prefix("\r\n").ok_or_else(parse_error)?;
+            body.push_str(chunk_data);
+            data = after_chunk;
+        }
+
+        // What remains is the trailer-section, a (possibly empty) run of
+        // field-lines followed by the final terminating CRLF.
+        let trailer_section = data.strip_suffix("\r\n").ok_or_else(parse_error)?;
+        let trailer_section = trailer_section
+            .strip_suffix("\r\n")
+            .unwrap_or(trailer_section);
+
+        let trailers = if trailer_section.is_empty() {
+            vec![]
+        } else {
+            Header::from_section(trailer_section.to_string())?
+        };
+
+        Ok((body, trailers))
+    }
+
     fn parse_request_line(request_line: &str) -> Result<(Method, &str, &str), Error> {
         let parse_error = Error {
             error: ErrorType::ParseError,
             error_msg: "invalid request-line format".to_string(),
         };
-        let mut parts = request_line.split(" ").into_iter();
+        let mut parts = request_line.split(" ");
 
         let method = match parts.next() {
             Some(v) => Method::from_string(v)?,
@@ -113,62 +319,240 @@ impl Request {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Header, Method, Request};
+    use crate::{Cookie, ErrorType, Method, Request};
 
     #[test]
     fn parse_get_request() {
-        let request_string = "GET / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nAccept: */*\r\nContent-Type: application/json\r\nContent-Length: 23";
+        let request_string =
+            "GET / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nAccept: */*\r\nContent-Type: application/json";
         let request = Request::from_string(request_string).unwrap();
 
         assert_eq!(request.method, Method::Get);
         assert_eq!(request.request_target, String::from("/"));
         assert_eq!(request.protocol_version, String::from("HTTP/1.1"));
+        assert_eq!(request.header.get("Host"), Some("127.0.0.1:3000"));
+        assert_eq!(request.header.get("Accept"), Some("*/*"));
+        assert_eq!(request.content_type(), Some("application/json"));
+        assert_eq!(request.body, None)
+    }
+
+    #[test]
+    fn parse_post_request() {
+        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nAccept: */*\r\nContent-Type: application/json\r\nContent-Length: 18\r\n\r\n{\"hello\": \"world\"}";
+
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.method, Method::Post);
+        assert_eq!(request.request_target, String::from("/"));
+        assert_eq!(request.protocol_version, String::from("HTTP/1.1"));
+        assert_eq!(request.content_length(), Some(18));
+        assert_eq!(request.body, Some("{\"hello\": \"world\"}".to_string()))
+    }
+
+    #[test]
+    fn parse_request_body_containing_crlf() {
+        let body = "line one\r\nline two\r\nline three";
+        let request_string = format!(
+            "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+
+        let request = Request::from_string(&request_string).unwrap();
+
+        assert_eq!(request.body, Some(body.to_string()))
+    }
+
+    #[test]
+    fn parse_request_missing_content_length_has_no_body() {
+        let request_string = "GET / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\n\r\nleftover";
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.body, None)
+    }
+
+    #[test]
+    fn parse_request_body_shorter_than_content_length() {
+        let request_string =
+            "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nContent-Length: 23\r\n\r\ntoo short";
+        let error = Request::from_string(request_string).unwrap_err();
+
+        assert_eq!(error.error, ErrorType::ParseError)
+    }
+
+    #[test]
+    fn parse_chunked_request_body() {
+        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nTransfer-Encoding: chunked\r\n\r\n7\r\nMozilla\r\n9\r\nDeveloper\r\n0\r\n\r\n";
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.body, Some("MozillaDeveloper".to_string()))
+    }
+
+    #[test]
+    fn parse_chunked_request_body_ignores_chunk_extensions() {
+        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nTransfer-Encoding: chunked\r\n\r\n4;foo=bar\r\nWiki\r\n0\r\n\r\n";
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.body, Some("Wiki".to_string()))
+    }
+
+    #[test]
+    fn parse_chunked_request_body_with_trailers() {
+        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\nExpires: Wed, 21 Oct 2015 07:28:00 GMT\r\n\r\n";
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.body, Some("hello".to_string()));
         assert_eq!(
-            request.header[0],
-            Header {
-                field_name: String::from("Host"),
-                field_value: String::from("127.0.0.1:3000")
-            }
+            request.header.get("Expires"),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT")
         );
+    }
+
+    #[test]
+    fn parse_chunked_request_ignores_content_length() {
+        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nContent-Length: 999\r\nTransfer-Encoding: chunked\r\n\r\n2\r\nhi\r\n0\r\n\r\n";
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.body, Some("hi".to_string()))
+    }
+
+    #[test]
+    fn parse_chunked_request_body_non_hex_size_is_error() {
+        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\nhi\r\n0\r\n\r\n";
+        let error = Request::from_string(request_string).unwrap_err();
+
+        assert_eq!(error.error, ErrorType::ParseError)
+    }
+
+    #[test]
+    fn parse_chunked_request_body_truncated_chunk_is_error() {
+        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nTransfer-Encoding: chunked\r\n\r\n10\r\nhi\r\n0\r\n\r\n";
+        let error = Request::from_string(request_string).unwrap_err();
+
+        assert_eq!(error.error, ErrorType::ParseError)
+    }
+
+    #[test]
+    fn cookies_parses_cookie_header() {
+        let request_string =
+            "GET / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nCookie: session=abc123; theme=dark\r\n\r\n";
+        let request = Request::from_string(request_string).unwrap();
+
         assert_eq!(
-            request.header[1],
-            Header {
-                field_name: String::from("Accept"),
-                field_value: String::from("*/*")
-            }
+            request.cookies().unwrap(),
+            vec![Cookie::new("session", "abc123"), Cookie::new("theme", "dark")]
         );
+    }
+
+    #[test]
+    fn cookies_is_empty_without_cookie_header() {
+        let request_string = "GET / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\n\r\n";
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.cookies().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn parse_splits_path_and_query() {
+        let request_string = "GET /search?q=hello+world&page=2 HTTP/1.1\r\nHost: 127.0.0.1:3000\r\n\r\n";
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.request_target, "/search?q=hello+world&page=2");
+        assert_eq!(request.path, "/search");
         assert_eq!(
-            request.header[2],
-            Header {
-                field_name: String::from("Content-Type"),
-                field_value: String::from("application/json")
-            }
+            request.query(),
+            &[
+                ("q".to_string(), "hello world".to_string()),
+                ("page".to_string(), "2".to_string())
+            ]
         );
+        assert_eq!(request.query_param("q"), Some("hello world"));
+        assert_eq!(request.query_param("missing"), None);
+    }
+
+    #[test]
+    fn parse_percent_decodes_query_values() {
+        let request_string =
+            "GET /search?tag=rust%26fun HTTP/1.1\r\nHost: 127.0.0.1:3000\r\n\r\n";
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.query_param("tag"), Some("rust&fun"));
+    }
+
+    #[test]
+    fn parse_percent_decodes_path() {
+        let request_string = "GET /a%20b HTTP/1.1\r\nHost: 127.0.0.1:3000\r\n\r\n";
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.path, "/a b");
+    }
+
+    #[test]
+    fn parse_request_with_no_query_has_empty_query_list() {
+        let request_string = "GET / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\n\r\n";
+        let request = Request::from_string(request_string).unwrap();
+
+        assert_eq!(request.path, "/");
+        assert_eq!(request.query(), &[]);
+    }
+
+    #[test]
+    fn parse_invalid_percent_escape_in_query_is_error() {
+        let request_string = "GET /search?q=%zz HTTP/1.1\r\nHost: 127.0.0.1:3000\r\n\r\n";
+        let error = Request::from_string(request_string).unwrap_err();
+
+        assert_eq!(error.error, ErrorType::ParseError)
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod json_tests {
+    use crate::{ErrorType, Request};
+
+    #[derive(serde::Deserialize, PartialEq, Eq, Debug)]
+    struct Greeting {
+        hello: String,
+    }
+
+    #[test]
+    fn json_deserializes_body() {
+        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nContent-Type: application/json\r\nContent-Length: 18\r\n\r\n{\"hello\": \"world\"}";
+        let request = Request::from_string(request_string).unwrap();
+
+        let greeting: Greeting = request.json().unwrap();
+
         assert_eq!(
-            request.header[3],
-            Header {
-                field_name: String::from("Content-Length"),
-                field_value: String::from("23")
+            greeting,
+            Greeting {
+                hello: "world".to_string()
             }
-        )
+        );
     }
 
     #[test]
-    fn parse_post_request() {
-        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nAccept: */*\r\nContent-Type: application/json\r\nContent-Length: 18\r\n\r\n{\"hello\": \"world\"}";
+    fn json_rejects_other_content_types() {
+        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nContent-Type: text/plain\r\nContent-Length: 18\r\n\r\n{\"hello\": \"world\"}";
+        let request = Request::from_string(request_string).unwrap();
+
+        let error = request.json::<Greeting>().unwrap_err();
 
+        assert_eq!(error.error, ErrorType::ParseError)
+    }
+
+    #[test]
+    fn json_with_content_types_accepts_extra_types() {
+        let request_string = "POST / HTTP/1.1\r\nHost: 127.0.0.1:3000\r\nContent-Type: application/vnd.api+json\r\nContent-Length: 18\r\n\r\n{\"hello\": \"world\"}";
         let request = Request::from_string(request_string).unwrap();
 
-        assert_eq!(request.method, Method::Post);
-        assert_eq!(request.request_target, String::from("/"));
-        assert_eq!(request.protocol_version, String::from("HTTP/1.1"));
+        let greeting: Greeting = request
+            .json_with_content_types(&["application/json", "application/vnd.api+json"])
+            .unwrap();
+
         assert_eq!(
-            request.header[3],
-            Header {
-                field_name: String::from("Content-Length"),
-                field_value: String::from("18")
+            greeting,
+            Greeting {
+                hello: "world".to_string()
             }
         );
-        assert_eq!(request.body, Some("{\"hello\": \"world\"}".to_string()))
     }
 }
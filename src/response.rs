@@ -1,11 +1,14 @@
 use std::fmt::Display;
+use std::time::SystemTime;
 
-use crate::{Header, Status};
+#[cfg(feature = "json")]
+use crate::{Error, ErrorType};
+use crate::{http_date, Cookie, Header, HeaderMap, Request, Status};
 
 pub struct Response {
     protocol_version: String,
     status: Status,
-    header: Vec<Header>,
+    header: HeaderMap,
     body: Option<String>,
 }
 
@@ -22,10 +25,6 @@ impl Default for Response {
 
 impl Display for Response {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut headers = String::new();
-        for h in &self.header {
-            headers.push_str(&h.to_string())
-        }
         let body = match &self.body {
             Some(v) => v.to_owned(),
             None => String::from(""),
@@ -33,7 +32,7 @@ impl Display for Response {
         write!(
             f,
             "{} {}\r\n{}\r\n{}",
-            self.protocol_version, self.status, headers, body
+            self.protocol_version, self.status, self.header, body
         )
     }
 }
@@ -49,16 +48,77 @@ impl Response {
 
     pub fn header(self, h: Header) -> Self {
         let mut header = self.header;
-        header.push(h);
+        header.append(&h.field_name, &h.field_value);
+        Response { header, ..self }
+    }
+
+    /// Adds a `Set-Cookie` header for `cookie`. Can be called multiple
+    /// times to set several cookies.
+    pub fn cookie(self, cookie: Cookie) -> Self {
+        let mut header = self.header;
+        header.append("Set-Cookie", &cookie.to_string());
+        Response { header, ..self }
+    }
+
+    /// Sets the `ETag` header, quoting `etag` if it isn't already a quoted
+    /// string or a weak (`W/"..."`) validator.
+    pub fn etag(self, etag: &str) -> Self {
+        let value = if etag.starts_with('"') || etag.starts_with("W/") {
+            etag.to_string()
+        } else {
+            format!("\"{}\"", etag)
+        };
+
+        let mut header = self.header;
+        header.insert("ETag", &value);
+        Response { header, ..self }
+    }
+
+    /// Sets the `Last-Modified` header, formatted as an IMF-fixdate.
+    pub fn last_modified(self, time: SystemTime) -> Self {
+        let mut header = self.header;
+        header.insert("Last-Modified", &http_date::format_imf_fixdate(time));
         Response { header, ..self }
     }
 
+    /// Honors `request`'s `If-None-Match`/`If-Modified-Since` headers
+    /// against the `ETag`/`Last-Modified` already set on this response: if
+    /// the resource hasn't changed, clears the body and sets status `304
+    /// Not Modified`. Per RFC 9110 §13.1.1, `If-None-Match` takes
+    /// precedence and `If-Modified-Since` is ignored when both are present.
+    pub fn not_modified_if(self, request: &Request) -> Self {
+        let not_modified = match request.header.get("if-none-match") {
+            Some(if_none_match) => etag_matches(if_none_match, self.header.get("etag")),
+            None => match (
+                request.header.get("if-modified-since"),
+                self.header.get("last-modified"),
+            ) {
+                (Some(if_modified_since), Some(last_modified)) => matches!(
+                    (
+                        http_date::parse_imf_fixdate(if_modified_since),
+                        http_date::parse_imf_fixdate(last_modified),
+                    ),
+                    (Ok(since), Ok(modified)) if modified <= since
+                ),
+                _ => false,
+            },
+        };
+
+        if not_modified {
+            Response {
+                status: Status::NotModified,
+                body: None,
+                ..self
+            }
+        } else {
+            self
+        }
+    }
+
     pub fn content(self, content: &str, content_type: &str) -> Response {
-        let content_length_header = Header::new("Content-Length", &content.len().to_string());
-        let content_type_header = Header::new("Content-Type", content_type);
-        let mut header = vec![];
-        header.push(content_length_header);
-        header.push(content_type_header);
+        let mut header = self.header;
+        header.insert("Content-Length", &content.len().to_string());
+        header.insert("Content-Type", content_type);
 
         Self {
             header,
@@ -71,14 +131,51 @@ impl Response {
         Self::content(self, content, "text/html")
     }
 
+    #[cfg(not(feature = "json"))]
     pub fn json(self, content: &str) -> Self {
         Self::content(self, content, "application/json")
     }
+
+    /// Serializes `value` as the response body and sets `Content-Type:
+    /// application/json` plus a matching `Content-Length`.
+    #[cfg(feature = "json")]
+    pub fn json<T: serde::Serialize>(self, value: &T) -> Result<Self, Error> {
+        let encoded = serde_json::to_string(value).map_err(|e| Error {
+            error: ErrorType::SerializationError,
+            error_msg: e.to_string(),
+        })?;
+
+        Ok(self.content(&encoded, "application/json"))
+    }
+}
+
+/// Strong `ETag` comparison (RFC 9110 §8.8.3.2): `*` matches anything, a
+/// weak response validator never matches, and otherwise `if_none_match`
+/// (a comma-separated list) must contain `etag` verbatim.
+fn etag_matches(if_none_match: &str, etag: Option<&str>) -> bool {
+    let if_none_match = if_none_match.trim();
+    if if_none_match == "*" {
+        return true;
+    }
+
+    let Some(etag) = etag else {
+        return false;
+    };
+    if etag.starts_with("W/") {
+        return false;
+    }
+
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == etag)
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{Header, Response, Status};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::{Cookie, Header, Request, Response, Status};
 
     #[test]
     fn response_without_body() {
@@ -112,6 +209,116 @@ mod test {
     }
 
     #[test]
+    fn response_content_keeps_headers_set_before_it() {
+        let response = Response::new()
+            .status(Status::Ok)
+            .header(Header::new("X-Request-Id", "abc123"))
+            .html("<HTML></HTML>");
+
+        assert_eq!(
+            response.to_string(),
+            "HTTP/1.1 200 OK\r\nX-Request-Id: abc123\r\nContent-Length: 13\r\nContent-Type: text/html\r\n\r\n<HTML></HTML>"
+        )
+    }
+
+    #[test]
+    fn response_with_cookie() {
+        let response = Response::new()
+            .status(Status::Ok)
+            .cookie(Cookie::new("session", "abc123").path("/").http_only());
+
+        assert_eq!(
+            response.to_string(),
+            "HTTP/1.1 200 OK\r\nSet-Cookie: session=abc123; Path=/; HttpOnly\r\n\r\n"
+        )
+    }
+
+    #[test]
+    fn response_with_etag_and_last_modified() {
+        let response = Response::new()
+            .status(Status::Ok)
+            .etag("abc123")
+            .last_modified(UNIX_EPOCH + Duration::from_secs(784111777));
+
+        assert_eq!(
+            response.to_string(),
+            "HTTP/1.1 200 OK\r\nETag: \"abc123\"\r\nLast-Modified: Sun, 06 Nov 1994 08:49:37 GMT\r\n\r\n"
+        )
+    }
+
+    #[test]
+    fn not_modified_if_matches_etag() {
+        let request =
+            Request::from_string("GET / HTTP/1.1\r\nIf-None-Match: \"abc123\"\r\n\r\n").unwrap();
+        let response = Response::new()
+            .status(Status::Ok)
+            .etag("abc123")
+            .not_modified_if(&request);
+
+        assert_eq!(
+            response.to_string(),
+            "HTTP/1.1 304 Not Modified\r\nETag: \"abc123\"\r\n\r\n"
+        )
+    }
+
+    #[test]
+    fn not_modified_if_matches_wildcard_etag() {
+        let request = Request::from_string("GET / HTTP/1.1\r\nIf-None-Match: *\r\n\r\n").unwrap();
+        let response = Response::new()
+            .status(Status::Ok)
+            .etag("abc123")
+            .not_modified_if(&request);
+
+        assert_eq!(response.to_string(), "HTTP/1.1 304 Not Modified\r\nETag: \"abc123\"\r\n\r\n")
+    }
+
+    #[test]
+    fn not_modified_if_etag_mismatch_keeps_response() {
+        let request =
+            Request::from_string("GET / HTTP/1.1\r\nIf-None-Match: \"other\"\r\n\r\n").unwrap();
+        let response = Response::new()
+            .status(Status::Ok)
+            .etag("abc123")
+            .html("<p>hi</p>")
+            .not_modified_if(&request);
+
+        assert_eq!(
+            response.to_string(),
+            "HTTP/1.1 200 OK\r\nETag: \"abc123\"\r\nContent-Length: 9\r\nContent-Type: text/html\r\n\r\n<p>hi</p>"
+        )
+    }
+
+    #[test]
+    fn not_modified_if_honors_if_modified_since() {
+        let request = Request::from_string(
+            "GET / HTTP/1.1\r\nIf-Modified-Since: Mon, 07 Nov 1994 00:00:00 GMT\r\n\r\n",
+        )
+        .unwrap();
+        let response = Response::new()
+            .status(Status::Ok)
+            .last_modified(UNIX_EPOCH + Duration::from_secs(784111777))
+            .not_modified_if(&request);
+
+        assert_eq!(response.to_string(), "HTTP/1.1 304 Not Modified\r\nLast-Modified: Sun, 06 Nov 1994 08:49:37 GMT\r\n\r\n")
+    }
+
+    #[test]
+    fn not_modified_if_ignores_if_modified_since_when_if_none_match_present() {
+        let request = Request::from_string(
+            "GET / HTTP/1.1\r\nIf-None-Match: \"other\"\r\nIf-Modified-Since: Mon, 07 Nov 1994 00:00:00 GMT\r\n\r\n",
+        )
+        .unwrap();
+        let response = Response::new()
+            .status(Status::Ok)
+            .etag("abc123")
+            .last_modified(UNIX_EPOCH + Duration::from_secs(784111777))
+            .not_modified_if(&request);
+
+        assert_eq!(response.status.code(), 200)
+    }
+
+    #[test]
+    #[cfg(not(feature = "json"))]
     fn response_with_json_body() {
         let response = Response::new()
             .status(Status::Ok)
@@ -123,3 +330,28 @@ mod test {
         )
     }
 }
+
+#[cfg(all(test, feature = "json"))]
+mod json_test {
+    use crate::{Response, Status};
+
+    #[derive(serde::Serialize)]
+    struct Greeting {
+        hello: String,
+    }
+
+    #[test]
+    fn response_with_json_body() {
+        let response = Response::new()
+            .status(Status::Ok)
+            .json(&Greeting {
+                hello: "world".to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            response.to_string(),
+            "HTTP/1.1 200 OK\r\nContent-Length: 17\r\nContent-Type: application/json\r\n\r\n{\"hello\":\"world\"}"
+        )
+    }
+}
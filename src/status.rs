@@ -1,38 +1,154 @@
 use std::fmt::Display;
 
+use crate::{Error, ErrorType};
+
+/// An HTTP status-code together with its canonical reason phrase.
+///
+/// status-line = HTTP-version SP status-code SP reason-phrase
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Status {
+    Continue,
+    SwitchingProtocols,
     Ok,
+    Created,
+    Accepted,
+    NoContent,
+    MovedPermanently,
+    Found,
     SeeOther,
+    NotModified,
+    TemporaryRedirect,
+    PermanentRedirect,
     BadRequest,
+    Unauthorized,
+    Forbidden,
     NotFound,
+    MethodNotAllowed,
+    Conflict,
+    Gone,
+    UnprocessableEntity,
+    TooManyRequests,
     InternalServerError,
+    NotImplemented,
+    BadGateway,
+    ServiceUnavailable,
+    GatewayTimeout,
+    /// Any status-code outside the set above, preserved verbatim. Lets a
+    /// client or proxy round-trip a status-line it doesn't otherwise know.
+    Other(u16),
 }
 
 impl Status {
-    fn status_code(&self) -> usize {
+    /// Builds a `Status` from a numeric status-code, e.g. one read off a
+    /// status-line on the wire. `code` must be in the `100..=599` range.
+    pub fn from_code(code: u16) -> Result<Status, Error> {
+        let status = match code {
+            100 => Status::Continue,
+            101 => Status::SwitchingProtocols,
+            200 => Status::Ok,
+            201 => Status::Created,
+            202 => Status::Accepted,
+            204 => Status::NoContent,
+            301 => Status::MovedPermanently,
+            302 => Status::Found,
+            303 => Status::SeeOther,
+            304 => Status::NotModified,
+            307 => Status::TemporaryRedirect,
+            308 => Status::PermanentRedirect,
+            400 => Status::BadRequest,
+            401 => Status::Unauthorized,
+            403 => Status::Forbidden,
+            404 => Status::NotFound,
+            405 => Status::MethodNotAllowed,
+            409 => Status::Conflict,
+            410 => Status::Gone,
+            422 => Status::UnprocessableEntity,
+            429 => Status::TooManyRequests,
+            500 => Status::InternalServerError,
+            501 => Status::NotImplemented,
+            502 => Status::BadGateway,
+            503 => Status::ServiceUnavailable,
+            504 => Status::GatewayTimeout,
+            code if (100..=599).contains(&code) => Status::Other(code),
+            _ => {
+                return Err(Error {
+                    error: ErrorType::ParseError,
+                    error_msg: format!("status code {} out of range", code),
+                })
+            }
+        };
+
+        Ok(status)
+    }
+
+    pub fn code(&self) -> u16 {
         match self {
+            Status::Continue => 100,
+            Status::SwitchingProtocols => 101,
             Status::Ok => 200,
+            Status::Created => 201,
+            Status::Accepted => 202,
+            Status::NoContent => 204,
+            Status::MovedPermanently => 301,
+            Status::Found => 302,
             Status::SeeOther => 303,
+            Status::NotModified => 304,
+            Status::TemporaryRedirect => 307,
+            Status::PermanentRedirect => 308,
             Status::BadRequest => 400,
+            Status::Unauthorized => 401,
+            Status::Forbidden => 403,
             Status::NotFound => 404,
+            Status::MethodNotAllowed => 405,
+            Status::Conflict => 409,
+            Status::Gone => 410,
+            Status::UnprocessableEntity => 422,
+            Status::TooManyRequests => 429,
             Status::InternalServerError => 500,
+            Status::NotImplemented => 501,
+            Status::BadGateway => 502,
+            Status::ServiceUnavailable => 503,
+            Status::GatewayTimeout => 504,
+            Status::Other(code) => *code,
         }
     }
 
-    fn msg(&self) -> &str {
+    pub fn reason(&self) -> &str {
         match self {
+            Status::Continue => "Continue",
+            Status::SwitchingProtocols => "Switching Protocols",
             Status::Ok => "OK",
-            Status::SeeOther => "SEE OTHER",
-            Status::BadRequest => "BAD REQUEST",
-            Status::NotFound => "NOT FOUND",
-            Status::InternalServerError => "INTERNAL SERVER ERROR",
+            Status::Created => "Created",
+            Status::Accepted => "Accepted",
+            Status::NoContent => "No Content",
+            Status::MovedPermanently => "Moved Permanently",
+            Status::Found => "Found",
+            Status::SeeOther => "See Other",
+            Status::NotModified => "Not Modified",
+            Status::TemporaryRedirect => "Temporary Redirect",
+            Status::PermanentRedirect => "Permanent Redirect",
+            Status::BadRequest => "Bad Request",
+            Status::Unauthorized => "Unauthorized",
+            Status::Forbidden => "Forbidden",
+            Status::NotFound => "Not Found",
+            Status::MethodNotAllowed => "Method Not Allowed",
+            Status::Conflict => "Conflict",
+            Status::Gone => "Gone",
+            Status::UnprocessableEntity => "Unprocessable Entity",
+            Status::TooManyRequests => "Too Many Requests",
+            Status::InternalServerError => "Internal Server Error",
+            Status::NotImplemented => "Not Implemented",
+            Status::BadGateway => "Bad Gateway",
+            Status::ServiceUnavailable => "Service Unavailable",
+            Status::GatewayTimeout => "Gateway Timeout",
+            Status::Other(_) => "",
         }
     }
 }
 
 impl Display for Status {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {}", self.status_code(), self.msg())
+        write!(f, "{} {}", self.code(), self.reason())
     }
 }
 
@@ -40,33 +156,109 @@ impl Display for Status {
 mod test {
     use crate::Status;
 
-    fn codes() -> Vec<(Status, usize, &'static str, &'static str)> {
+    fn codes() -> Vec<(Status, u16, &'static str, &'static str)> {
         vec![
+            (Status::Continue, 100, "Continue", "100 Continue"),
             (Status::Ok, 200, "OK", "200 OK"),
-            (Status::SeeOther, 303, "SEE OTHER", "303 SEE OTHER"),
-            (Status::BadRequest, 400, "BAD REQUEST", "400 BAD REQUEST"),
-            (Status::NotFound, 404, "NOT FOUND", "404 NOT FOUND"),
+            (Status::Created, 201, "Created", "201 Created"),
+            (Status::NoContent, 204, "No Content", "204 No Content"),
+            (
+                Status::MovedPermanently,
+                301,
+                "Moved Permanently",
+                "301 Moved Permanently",
+            ),
+            (Status::Found, 302, "Found", "302 Found"),
+            (Status::SeeOther, 303, "See Other", "303 See Other"),
+            (
+                Status::NotModified,
+                304,
+                "Not Modified",
+                "304 Not Modified",
+            ),
+            (Status::BadRequest, 400, "Bad Request", "400 Bad Request"),
+            (
+                Status::Unauthorized,
+                401,
+                "Unauthorized",
+                "401 Unauthorized",
+            ),
+            (Status::NotFound, 404, "Not Found", "404 Not Found"),
+            (
+                Status::MethodNotAllowed,
+                405,
+                "Method Not Allowed",
+                "405 Method Not Allowed",
+            ),
+            (Status::Conflict, 409, "Conflict", "409 Conflict"),
+            (
+                Status::UnprocessableEntity,
+                422,
+                "Unprocessable Entity",
+                "422 Unprocessable Entity",
+            ),
+            (
+                Status::TooManyRequests,
+                429,
+                "Too Many Requests",
+                "429 Too Many Requests",
+            ),
             (
                 Status::InternalServerError,
                 500,
-                "INTERNAL SERVER ERROR",
-                "500 INTERNAL SERVER ERROR",
+                "Internal Server Error",
+                "500 Internal Server Error",
+            ),
+            (Status::BadGateway, 502, "Bad Gateway", "502 Bad Gateway"),
+            (
+                Status::ServiceUnavailable,
+                503,
+                "Service Unavailable",
+                "503 Service Unavailable",
             ),
         ]
     }
 
     #[test]
-    fn status_code() {
-        for (status, code, msg, _) in codes() {
-            assert_eq!(status.status_code(), code);
-            assert_eq!(status.msg(), msg);
+    fn code() {
+        for (status, code, _, _) in codes() {
+            assert_eq!(status.code(), code);
+        }
+    }
+
+    #[test]
+    fn reason() {
+        for (status, _, reason, _) in codes() {
+            assert_eq!(status.reason(), reason);
         }
     }
 
     #[test]
-    fn msg() {
+    fn display() {
         for (status, _, _, to_string) in codes() {
             assert_eq!(status.to_string(), to_string)
         }
     }
+
+    #[test]
+    fn from_code_known() {
+        for (status, code, _, _) in codes() {
+            assert_eq!(Status::from_code(code).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn from_code_unknown_is_other() {
+        let status = Status::from_code(499).unwrap();
+
+        assert_eq!(status, Status::Other(499));
+        assert_eq!(status.code(), 499);
+        assert_eq!(status.to_string(), "499 ");
+    }
+
+    #[test]
+    fn from_code_out_of_range_is_error() {
+        assert!(Status::from_code(42).is_err());
+        assert!(Status::from_code(600).is_err());
+    }
 }